@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::error::AppError;
+use crate::hotkey;
+use crate::launch;
+use crate::presentation;
+use crate::summon;
+use crate::windows;
+
+/// Metadata the command palette renders for each backend action.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionInfo {
+    id: &'static str,
+    title: &'static str,
+    description: &'static str,
+    /// Names of the fields `run_action` expects in `args`.
+    args: &'static [&'static str],
+}
+
+const ACTIONS: &[ActionInfo] = &[
+    ActionInfo {
+        id: "toggle-window",
+        title: "Toggle Window",
+        description: "Hide the main window, or bring it forward if hidden.",
+        args: &[],
+    },
+    ActionInfo {
+        id: "new-thread",
+        title: "New Thread",
+        description: "Start a new thread in the focused conversation window, or the main window.",
+        args: &[],
+    },
+    ActionInfo {
+        id: "resume-last",
+        title: "Resume Last Thread",
+        description: "Return to the most recent thread.",
+        args: &[],
+    },
+    ActionInfo {
+        id: "toggle-mini",
+        title: "Toggle Mini Window",
+        description: "Shrink the main window to a corner card, or expand it back.",
+        args: &[],
+    },
+    ActionInfo {
+        id: "new-chat-from-clipboard",
+        title: "New Chat with Clipboard",
+        description: "Start a new chat seeded with the clipboard text.",
+        args: &[],
+    },
+    ActionInfo {
+        id: "open-conversation-window",
+        title: "Open in New Window",
+        description: "Open a conversation in its own window.",
+        args: &["conversationId"],
+    },
+    ActionInfo {
+        id: "pause-hotkeys",
+        title: "Pause Hotkeys",
+        description: "Unregister global shortcuts until resumed.",
+        args: &[],
+    },
+    ActionInfo {
+        id: "resume-hotkeys",
+        title: "Resume Hotkeys",
+        description: "Register global shortcuts again.",
+        args: &[],
+    },
+];
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionArgs {
+    conversation_id: Option<String>,
+}
+
+/// Lists every action the shell can run, so the command palette is generated
+/// from the backend instead of being maintained by hand.
+#[tauri::command]
+pub fn list_actions() -> Vec<ActionInfo> {
+    ACTIONS.to_vec()
+}
+
+#[tauri::command]
+pub async fn run_action(
+    app: AppHandle,
+    id: String,
+    args: Option<ActionArgs>,
+) -> Result<(), AppError> {
+    let args = args.unwrap_or_default();
+    match id.as_str() {
+        "toggle-window" => summon::toggle(&app)?,
+        "new-thread" => summon::new_thread(&app)?,
+        "resume-last" => summon::resume_last(&app)?,
+        "toggle-mini" => {
+            presentation::toggle_mini(&app)?;
+        }
+        "new-chat-from-clipboard" => launch::new_chat_from_clipboard(app)?,
+        "open-conversation-window" => {
+            let conversation_id = args
+                .conversation_id
+                .ok_or(AppError::MissingActionArgument("conversationId"))?;
+            windows::open_conversation(&app, &conversation_id)?;
+        }
+        "pause-hotkeys" => hotkey::set_hotkeys_paused(app, true)?,
+        "resume-hotkeys" => hotkey::set_hotkeys_paused(app, false)?,
+        _ => return Err(AppError::UnknownAction(id)),
+    }
+    Ok(())
+}
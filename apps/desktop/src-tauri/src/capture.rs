@@ -0,0 +1,94 @@
+use serde::Serialize;
+use tauri::{
+    AppHandle, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder, Window, WindowEvent,
+};
+
+use crate::error::AppError;
+use crate::outbox;
+use crate::windows::MAIN_WINDOW_LABEL;
+
+pub const QUICK_CAPTURE_LABEL: &str = "quick-capture";
+const QUICK_CAPTURE_EVENT: &str = "quick-capture";
+const QUICK_CAPTURE_WIDTH: f64 = 480.0;
+const QUICK_CAPTURE_HEIGHT: f64 = 120.0;
+pub const MAX_CAPTURE_LENGTH: usize = 100_000;
+
+#[derive(Serialize)]
+struct QuickCapture {
+    content: String,
+}
+
+fn build_window(app: &AppHandle) -> tauri::Result<WebviewWindow> {
+    WebviewWindowBuilder::new(
+        app,
+        QUICK_CAPTURE_LABEL,
+        WebviewUrl::App("quick-capture".into()),
+    )
+    .title("Quick Capture")
+    .inner_size(QUICK_CAPTURE_WIDTH, QUICK_CAPTURE_HEIGHT)
+    .resizable(false)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .center()
+    .visible(false)
+    .build()
+}
+
+/// Shows the quick-capture window (creating it on first use) or hides it if
+/// it is already visible. The main window is left untouched either way.
+pub fn toggle(app: &AppHandle) -> tauri::Result<()> {
+    let window = match app.get_webview_window(QUICK_CAPTURE_LABEL) {
+        Some(window) => window,
+        None => build_window(app)?,
+    };
+
+    if window.is_visible()? {
+        window.hide()
+    } else {
+        window.show()?;
+        window.set_focus()
+    }
+}
+
+/// Sends a capture to the main window as a `quick-capture` event, then hides
+/// the capture window. Filing it into a conversation is up to the web client.
+#[tauri::command]
+pub fn submit_quick_capture(app: AppHandle, content: String) -> Result<(), AppError> {
+    let content = content.trim();
+    if content.is_empty() || content.chars().count() > MAX_CAPTURE_LENGTH {
+        return Err(AppError::InvalidCaptureContent);
+    }
+
+    outbox::emit(
+        &app,
+        QUICK_CAPTURE_EVENT,
+        QuickCapture {
+            content: content.to_owned(),
+        },
+    )?;
+
+    if let Some(window) = app.get_webview_window(QUICK_CAPTURE_LABEL) {
+        window.hide()?;
+    }
+    Ok(())
+}
+
+/// The capture window never holds focus for long: it hides as soon as the
+/// user clicks away. It is also torn down with the main window, since a hidden
+/// capture window would otherwise keep the app running after `main` closes.
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    match event {
+        WindowEvent::Focused(false) if window.label() == QUICK_CAPTURE_LABEL => {
+            let _ = window.hide();
+        }
+        WindowEvent::Destroyed if window.label() == MAIN_WINDOW_LABEL => {
+            if let Some(capture) = window.get_webview_window(QUICK_CAPTURE_LABEL) {
+                if let Err(e) = capture.destroy() {
+                    eprintln!("failed to close quick-capture window: {e}");
+                }
+            }
+        }
+        _ => {}
+    }
+}
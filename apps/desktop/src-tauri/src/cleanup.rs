@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use tauri::{AppHandle, Manager};
+
+use crate::files;
+
+/// Suffixes left behind by interrupted atomic writes (see
+/// `files::write_atomic`, used for settings, window state, and the outbox).
+const TEMP_SUFFIXES: &[&str] = &[files::TEMP_SUFFIX];
+/// Temp files younger than this may still belong to a write in progress.
+const STALE_AFTER: Duration = Duration::from_secs(10 * 60);
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+fn sweep_dirs(app: &AppHandle) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = [app.path().app_config_dir(), app.path().app_data_dir()]
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect();
+    dirs.dedup();
+    dirs
+}
+
+fn is_temp_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| TEMP_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)))
+}
+
+fn is_stale(path: &Path, now: SystemTime) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| now.duration_since(modified).ok())
+        .is_some_and(|age| age >= STALE_AFTER)
+}
+
+/// Removes stale temp files directly inside `dir` and returns how many were
+/// removed. Not recursive: temp files are always written next to the file
+/// they replace.
+fn sweep_dir(dir: &Path, now: SystemTime) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+
+    let mut removed = 0;
+    for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+        if path.is_file()
+            && is_temp_file(&path)
+            && is_stale(&path, now)
+            && fs::remove_file(&path).is_ok()
+        {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+pub fn sweep(app: &AppHandle) {
+    let now = SystemTime::now();
+    for dir in sweep_dirs(app) {
+        sweep_dir(&dir, now);
+    }
+}
+
+/// Sweeps once at startup, then periodically so long-running sessions don't
+/// accumulate leftovers either.
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        sweep(&app);
+        thread::sleep(SWEEP_INTERVAL);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use super::*;
+
+    #[test]
+    fn sweep_dir_removes_only_stale_temp_files() {
+        let dir = std::env::temp_dir().join(format!("nosis-cleanup-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let now = SystemTime::now();
+        let long_ago = now - STALE_AFTER * 2;
+
+        for (name, modified) in [
+            ("settings.json.tmp", long_ago),
+            ("window-state.json.tmp", now),
+            ("settings.json", long_ago),
+        ] {
+            File::create(dir.join(name))
+                .and_then(|file| file.set_modified(modified))
+                .unwrap();
+        }
+
+        assert_eq!(sweep_dir(&dir, now), 1);
+        assert!(!dir.join("settings.json.tmp").exists());
+        assert!(dir.join("window-state.json.tmp").exists());
+        assert!(dir.join("settings.json").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Serialize, Serializer};
+
+use crate::capture::MAX_CAPTURE_LENGTH;
+
+/// Errors returned from IPC commands. Serialized as their display string so
+/// the webview receives a plain message rather than internal details.
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("Invalid conversation ID")]
+    InvalidConversationId,
+    #[error("Capture content must be between 1 and {max} characters", max = MAX_CAPTURE_LENGTH)]
+    InvalidCaptureContent,
+    #[error("Unknown action: {0}")]
+    UnknownAction(String),
+    #[error("Action requires argument: {0}")]
+    MissingActionArgument(&'static str),
+    #[error("Clipboard does not contain text")]
+    EmptyClipboard,
+    #[error("Clipboard text must be at most {max} characters", max = MAX_CAPTURE_LENGTH)]
+    ClipboardTooLong,
+    #[error("Failed to read clipboard")]
+    Clipboard(#[from] tauri_plugin_clipboard_manager::Error),
+    #[error("Invalid shortcut: {0}")]
+    InvalidShortcut(String),
+    #[error("Shortcut is bound more than once: {0}")]
+    DuplicateShortcut(String),
+    #[error("Shortcut is already in use: {0}")]
+    UnavailableShortcut(String),
+    #[error("Failed to update global shortcuts")]
+    Hotkey(#[from] tauri_plugin_global_shortcut::Error),
+    #[error("File operation failed")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to encode data")]
+    Json(#[from] serde_json::Error),
+    #[error("Window operation failed")]
+    Window(#[from] tauri::Error),
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
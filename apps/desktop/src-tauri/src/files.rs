@@ -0,0 +1,18 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Suffix of the sibling file `write_atomic` writes before renaming.
+pub const TEMP_SUFFIX: &str = ".tmp";
+
+/// Writes to a sibling temp file and renames it into place so a crash
+/// mid-write never leaves a truncated file behind.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(TEMP_SUFFIX);
+    let tmp = PathBuf::from(tmp);
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
@@ -0,0 +1,273 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use crate::capture;
+use crate::error::AppError;
+use crate::presentation;
+use crate::settings::SettingsState;
+use crate::summon;
+use crate::windows;
+
+#[derive(Default)]
+pub struct HotkeyState {
+    paused: AtomicBool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum HotkeyAction {
+    /// Bring the main window forward (or toggle it, see
+    /// `summonHotkeyToggles`).
+    Summon,
+    /// Summon the main window and start a new thread.
+    NewThread,
+    /// Summon the main window on the most recent thread.
+    ResumeLast,
+    QuickCapture,
+    /// Shrink the main window to the mini card, or expand it back.
+    ToggleMini,
+    /// Open a specific conversation in its own window.
+    OpenConversation {
+        #[serde(rename = "conversationId")]
+        conversation_id: String,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyBinding {
+    /// Accelerator string, e.g. `Alt+Space`.
+    pub shortcut: String,
+    pub action: HotkeyAction,
+}
+
+/// Quick capture has no default shortcut: the web client doesn't serve its
+/// `/quick-capture` route yet, so it is only reachable once bound by hand.
+pub fn default_bindings() -> Vec<HotkeyBinding> {
+    vec![HotkeyBinding {
+        shortcut: "Alt+Space".to_owned(),
+        action: HotkeyAction::Summon,
+    }]
+}
+
+fn parse_shortcut(shortcut: &str) -> Result<Shortcut, AppError> {
+    shortcut
+        .parse::<Shortcut>()
+        .map_err(|_| AppError::InvalidShortcut(shortcut.to_owned()))
+}
+
+/// Rejects unparseable accelerators, duplicate shortcuts, and malformed
+/// conversation IDs before they are saved.
+pub fn validate_bindings(bindings: &[HotkeyBinding]) -> Result<(), AppError> {
+    let mut seen = Vec::with_capacity(bindings.len());
+    for binding in bindings {
+        let shortcut = parse_shortcut(&binding.shortcut)?;
+        if seen.contains(&shortcut) {
+            return Err(AppError::DuplicateShortcut(binding.shortcut.clone()));
+        }
+        seen.push(shortcut);
+
+        if let HotkeyAction::OpenConversation { conversation_id } = &binding.action {
+            if !windows::is_valid_uuid(conversation_id) {
+                return Err(AppError::InvalidConversationId);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn bindings(app: &AppHandle) -> Vec<HotkeyBinding> {
+    app.try_state::<SettingsState>()
+        .map(|settings| settings.get().hotkeys)
+        .unwrap_or_else(default_bindings)
+}
+
+/// Registers every configured binding. Bindings that fail to parse or that
+/// the OS refuses (e.g. already taken by another app) are logged and skipped
+/// so one bad entry can't disable the rest.
+pub fn register(app: &AppHandle) {
+    register_bindings(app, &bindings(app));
+}
+
+/// Returns the shortcuts that could not be registered.
+fn register_bindings(app: &AppHandle, bindings: &[HotkeyBinding]) -> Vec<String> {
+    let shortcuts = app.global_shortcut();
+    let mut failed = Vec::new();
+    for binding in bindings {
+        let result = parse_shortcut(&binding.shortcut)
+            .and_then(|shortcut| shortcuts.register(shortcut).map_err(AppError::from));
+        if let Err(e) = result {
+            eprintln!("skipping hotkey binding {}: {e}", binding.shortcut);
+            failed.push(binding.shortcut.clone());
+        }
+    }
+    failed
+}
+
+/// Swaps the registered shortcuts for `bindings` and returns the ones the OS
+/// refused. While paused, nothing is registered; the bindings saved in
+/// settings take effect on resume instead.
+pub fn reload(
+    app: &AppHandle,
+    bindings: &[HotkeyBinding],
+) -> Result<Vec<String>, tauri_plugin_global_shortcut::Error> {
+    if is_paused(app) {
+        return Ok(Vec::new());
+    }
+    app.global_shortcut().unregister_all()?;
+    Ok(register_bindings(app, bindings))
+}
+
+pub fn is_paused(app: &AppHandle) -> bool {
+    app.state::<HotkeyState>().paused.load(Ordering::Acquire)
+}
+
+/// Pausing unregisters every shortcut so the key combinations reach other
+/// apps (e.g. during screen sharing or games) until resumed. Resuming still
+/// succeeds when some shortcuts were taken in the meantime, but reports them.
+pub fn set_paused(app: &AppHandle, paused: bool) -> Result<(), AppError> {
+    if is_paused(app) == paused {
+        return Ok(());
+    }
+
+    let failed = if paused {
+        app.global_shortcut().unregister_all()?;
+        Vec::new()
+    } else {
+        register_bindings(app, &bindings(app))
+    };
+    app.state::<HotkeyState>()
+        .paused
+        .store(paused, Ordering::Release);
+
+    if !failed.is_empty() {
+        return Err(AppError::UnavailableShortcut(failed.join(", ")));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_hotkeys_paused(app: AppHandle, paused: bool) -> Result<(), AppError> {
+    let result = set_paused(&app, paused);
+    #[cfg(target_os = "macos")]
+    crate::menubar::refresh_menu(&app)?;
+    result
+}
+
+fn run_action(app: &AppHandle, action: &HotkeyAction) -> Result<(), AppError> {
+    match action {
+        HotkeyAction::Summon => {
+            let toggles = app
+                .try_state::<SettingsState>()
+                .is_some_and(|settings| settings.get().summon_hotkey_toggles);
+            if toggles {
+                summon::toggle(app)?;
+            } else {
+                summon::summon(app)?;
+            }
+        }
+        HotkeyAction::NewThread => summon::new_thread(app)?,
+        HotkeyAction::ResumeLast => summon::resume_last(app)?,
+        HotkeyAction::QuickCapture => capture::toggle(app)?,
+        HotkeyAction::ToggleMini => {
+            presentation::toggle_mini(app)?;
+        }
+        HotkeyAction::OpenConversation { conversation_id } => {
+            windows::open_conversation(app, conversation_id)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn handle_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+
+    let Some(binding) = bindings(app)
+        .into_iter()
+        .find(|binding| parse_shortcut(&binding.shortcut).is_ok_and(|parsed| parsed == *shortcut))
+    else {
+        return;
+    };
+
+    if let Err(e) = run_action(app, &binding.action) {
+        eprintln!("failed to run hotkey action: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONVERSATION_ID: &str = "123e4567-e89b-12d3-a456-426614174000";
+
+    fn binding(shortcut: &str, action: HotkeyAction) -> HotkeyBinding {
+        HotkeyBinding {
+            shortcut: shortcut.to_owned(),
+            action,
+        }
+    }
+
+    #[test]
+    fn default_bindings_are_valid() {
+        assert!(validate_bindings(&default_bindings()).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_shortcut() {
+        let result = validate_bindings(&[binding("Alt+Nope", HotkeyAction::Summon)]);
+        assert!(matches!(result, Err(AppError::InvalidShortcut(s)) if s == "Alt+Nope"));
+    }
+
+    #[test]
+    fn rejects_duplicate_shortcut() {
+        let result = validate_bindings(&[
+            binding("Alt+Space", HotkeyAction::Summon),
+            binding("Alt+Space", HotkeyAction::NewThread),
+        ]);
+        assert!(matches!(result, Err(AppError::DuplicateShortcut(s)) if s == "Alt+Space"));
+    }
+
+    #[test]
+    fn rejects_bad_conversation_id() {
+        let result = validate_bindings(&[binding(
+            "Alt+O",
+            HotkeyAction::OpenConversation {
+                conversation_id: "not-a-uuid".to_owned(),
+            },
+        )]);
+        assert!(matches!(result, Err(AppError::InvalidConversationId)));
+    }
+
+    #[test]
+    fn accepts_valid_conversation_id() {
+        let result = validate_bindings(&[binding(
+            "Alt+O",
+            HotkeyAction::OpenConversation {
+                conversation_id: CONVERSATION_ID.to_owned(),
+            },
+        )]);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn open_conversation_action_serde_round_trip() {
+        let action = HotkeyAction::OpenConversation {
+            conversation_id: CONVERSATION_ID.to_owned(),
+        };
+        let json = serde_json::json!({
+            "type": "openConversation",
+            "conversationId": CONVERSATION_ID,
+        });
+
+        assert_eq!(serde_json::to_value(&action).unwrap(), json);
+        assert_eq!(
+            serde_json::from_value::<HotkeyAction>(json).unwrap(),
+            action
+        );
+    }
+}
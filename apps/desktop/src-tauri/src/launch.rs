@@ -0,0 +1,231 @@
+use std::path::Path;
+
+use serde::Serialize;
+use tauri::{AppHandle, Url};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+#[cfg(any(windows, target_os = "linux"))]
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::capture::MAX_CAPTURE_LENGTH;
+use crate::error::AppError;
+use crate::outbox;
+use crate::summon;
+
+const LAUNCH_REQUEST_EVENT: &str = "launch-request";
+const URL_SCHEME: &str = "nosis";
+
+/// What a second launch or OS open asked for, forwarded to the main window's
+/// web client to act on (open a link, attach files, send a prompt).
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchRequest {
+    links: Vec<String>,
+    files: Vec<String>,
+    prompt: Option<String>,
+}
+
+impl LaunchRequest {
+    /// Classifies CLI arguments: paths to existing files (resolved against the
+    /// caller's cwd), and everything else joined into a prompt, e.g.
+    /// `nosis "summarize this" notes.md`. `nosis://` links are skipped: the
+    /// single-instance plugin also hands argv to the deep-link plugin, which
+    /// delivers them through `handle_open_urls`.
+    fn from_args(args: &[String], cwd: &Path) -> Self {
+        let mut request = Self::default();
+        let mut prompt_parts = Vec::new();
+
+        for arg in args {
+            if arg.starts_with('-') || arg.starts_with(&format!("{URL_SCHEME}://")) {
+                continue;
+            }
+            let path = cwd.join(arg);
+            if path.is_file() {
+                request.files.push(path.to_string_lossy().into_owned());
+            } else {
+                prompt_parts.push(arg.as_str());
+            }
+        }
+
+        if !prompt_parts.is_empty() {
+            request.prompt = Some(prompt_parts.join(" "));
+        }
+        request
+    }
+
+    fn from_urls(urls: &[Url]) -> Self {
+        let mut request = Self::default();
+        for url in urls {
+            if url.scheme() == URL_SCHEME {
+                request.links.push(url.to_string());
+            } else if let Ok(path) = url.to_file_path() {
+                request.files.push(path.to_string_lossy().into_owned());
+            }
+        }
+        request
+    }
+
+    fn is_empty(&self) -> bool {
+        self.links.is_empty() && self.files.is_empty() && self.prompt.is_none()
+    }
+}
+
+/// Called by the single-instance plugin in the primary process when the app
+/// is launched again.
+pub fn handle_second_instance(app: &AppHandle, argv: Vec<String>, cwd: String) {
+    let args = argv.get(1..).unwrap_or_default();
+    dispatch(app, LaunchRequest::from_args(args, Path::new(&cwd)));
+}
+
+/// Called for `nosis://` links and files opened through the OS.
+pub fn handle_open_urls(app: &AppHandle, urls: &[Url]) {
+    dispatch(app, LaunchRequest::from_urls(urls));
+}
+
+/// On Windows and Linux a cold start from a link passes it as an argument
+/// rather than an open-URL event, so it has to be read once at startup.
+#[cfg(any(windows, target_os = "linux"))]
+pub fn handle_launch_urls(app: &AppHandle) {
+    match app.deep_link().get_current() {
+        Ok(Some(urls)) => handle_open_urls(app, &urls),
+        Ok(None) => {}
+        Err(e) => eprintln!("failed to read launch URL: {e}"),
+    }
+}
+
+/// Starts a new chat seeded with the clipboard text, capped at the same length
+/// as a quick capture. Lives in the backend so the menubar action works before
+/// the webview has finished loading.
+#[tauri::command]
+pub fn new_chat_from_clipboard(app: AppHandle) -> Result<(), AppError> {
+    let text = match app.clipboard().read_text() {
+        Ok(text) => text,
+        Err(e) if is_content_unavailable(&e) => return Err(AppError::EmptyClipboard),
+        Err(e) => return Err(e.into()),
+    };
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(AppError::EmptyClipboard);
+    }
+    if text.chars().count() > MAX_CAPTURE_LENGTH {
+        return Err(AppError::ClipboardTooLong);
+    }
+
+    dispatch(
+        &app,
+        LaunchRequest {
+            prompt: Some(text.to_owned()),
+            ..LaunchRequest::default()
+        },
+    );
+    Ok(())
+}
+
+/// An empty or image-only clipboard fails the text read with arboard's
+/// `ContentNotAvailable`, which the plugin only passes on as a message.
+fn is_content_unavailable(error: &tauri_plugin_clipboard_manager::Error) -> bool {
+    matches!(
+        error,
+        tauri_plugin_clipboard_manager::Error::Clipboard(message)
+            if message.contains("not available")
+    )
+}
+
+fn dispatch(app: &AppHandle, request: LaunchRequest) {
+    if let Err(e) = summon::summon(app) {
+        eprintln!("failed to summon window for launch request: {e}");
+    }
+    if request.is_empty() {
+        return;
+    }
+    if let Err(e) = outbox::emit(app, LAUNCH_REQUEST_EVENT, request) {
+        eprintln!("failed to forward launch request: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| (*arg).to_owned()).collect()
+    }
+
+    /// A scratch dir holding an empty `notes.md`.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("nosis-launch-{}-{name}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("notes.md"), b"").unwrap();
+        dir
+    }
+
+    #[test]
+    fn from_args_skips_flags_and_joins_prompt() {
+        let request = LaunchRequest::from_args(
+            &args(&["--new-window", "summarize", "-v", "this please"]),
+            Path::new("/nonexistent"),
+        );
+
+        assert!(request.links.is_empty());
+        assert!(request.files.is_empty());
+        assert_eq!(request.prompt.as_deref(), Some("summarize this please"));
+    }
+
+    #[test]
+    fn from_args_leaves_links_to_the_deep_link_plugin() {
+        let request = LaunchRequest::from_args(
+            &args(&["nosis://chat/123", "https://example.com"]),
+            Path::new("/nonexistent"),
+        );
+
+        assert!(request.links.is_empty());
+        assert_eq!(request.prompt.as_deref(), Some("https://example.com"));
+    }
+
+    #[test]
+    fn from_args_resolves_files_against_cwd() {
+        let dir = scratch_dir("relative");
+        let request = LaunchRequest::from_args(&args(&["notes.md", "missing.md"]), &dir);
+
+        assert_eq!(
+            request.files,
+            [dir.join("notes.md").to_string_lossy().into_owned()]
+        );
+        assert_eq!(request.prompt.as_deref(), Some("missing.md"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn from_args_keeps_absolute_files() {
+        let dir = scratch_dir("absolute");
+        let file = dir.join("notes.md");
+        let request =
+            LaunchRequest::from_args(&args(&[file.to_str().unwrap()]), Path::new("/nonexistent"));
+
+        assert_eq!(request.files, [file.to_string_lossy().into_owned()]);
+        assert!(request.prompt.is_none());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn from_urls_splits_links_and_files() {
+        let file = std::env::temp_dir().join("notes.md");
+        let urls = [
+            Url::parse("nosis://chat/123").unwrap(),
+            Url::from_file_path(&file).unwrap(),
+            Url::parse("https://example.com").unwrap(),
+        ];
+        let request = LaunchRequest::from_urls(&urls);
+
+        assert_eq!(request.links, ["nosis://chat/123"]);
+        assert_eq!(request.files, [file.to_string_lossy().into_owned()]);
+        assert!(request.prompt.is_none());
+    }
+
+    #[test]
+    fn empty_args_make_an_empty_request() {
+        assert!(LaunchRequest::from_args(&[], Path::new("/nonexistent")).is_empty());
+    }
+}
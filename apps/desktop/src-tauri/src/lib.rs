@@ -1,7 +1,109 @@
+use std::time::Instant;
+
+use tauri::Manager;
+use tauri_plugin_deep_link::DeepLinkExt;
+
+mod actions;
+mod capture;
+mod cleanup;
+mod error;
+mod files;
+mod hotkey;
+mod launch;
+#[cfg(target_os = "macos")]
+mod menubar;
+mod outbox;
+mod presentation;
+mod settings;
+mod shutdown;
+mod startup;
+mod summon;
+mod window_state;
+mod windows;
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let launched = Instant::now();
+
     tauri::Builder::default()
+        // Must be registered first so a second launch exits before any other
+        // plugin initializes.
+        .plugin(tauri_plugin_single_instance::init(
+            launch::handle_second_instance,
+        ))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
-        .run(tauri::generate_context!())
-        .expect("error while running nosis");
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(hotkey::handle_shortcut)
+                .build(),
+        )
+        .manage(windows::ConversationWindows::default())
+        .manage(summon::SummonState::default())
+        .manage(hotkey::HotkeyState::default())
+        .manage(presentation::PresentationState::default())
+        .setup(move |app| {
+            let mut timer = startup::StartupTimer::new(launched);
+
+            let shell_settings =
+                timer.phase("settings", || settings::SettingsState::load(app.handle()))?;
+            app.manage(shell_settings);
+            let event_outbox = timer.phase("outbox", || outbox::EventOutbox::load(app.handle()))?;
+            app.manage(event_outbox);
+            timer.phase("window_state", || window_state::restore(app.handle()));
+            timer.phase("hotkeys", || hotkey::register(app.handle()));
+            timer.phase("deep_link", || {
+                let handle = app.handle().clone();
+                app.deep_link()
+                    .on_open_url(move |event| launch::handle_open_urls(&handle, &event.urls()));
+                #[cfg(any(windows, target_os = "linux"))]
+                launch::handle_launch_urls(app.handle());
+            });
+            #[cfg(target_os = "macos")]
+            timer.phase("menubar", || {
+                app.manage(menubar::MenubarState::default());
+                menubar::init(app.handle())
+            })?;
+            // Not needed for the first frame, so it runs off the setup path.
+            cleanup::spawn(app.handle().clone());
+
+            app.manage(timer.finish());
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            windows::handle_window_event(window, event);
+            summon::handle_window_event(window, event);
+            window_state::handle_window_event(window, event);
+            capture::handle_window_event(window, event);
+            presentation::handle_window_event(window, event);
+            #[cfg(target_os = "macos")]
+            menubar::handle_window_event(window, event);
+        })
+        .invoke_handler(tauri::generate_handler![
+            windows::open_conversation_window,
+            windows::list_conversation_windows,
+            capture::submit_quick_capture,
+            settings::get_shell_settings,
+            settings::update_shell_settings,
+            summon::toggle_window,
+            hotkey::set_hotkeys_paused,
+            launch::new_chat_from_clipboard,
+            actions::list_actions,
+            actions::run_action,
+            startup::get_startup_report,
+            outbox::fetch_pending_events,
+            outbox::ack_events,
+            presentation::get_presentation,
+            presentation::set_peek_mode,
+            presentation::set_peek_expanded,
+            presentation::toggle_mini_mode,
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while building nosis")
+        .run(|app, event| {
+            #[cfg(target_os = "macos")]
+            summon::handle_run_event(app, &event);
+            shutdown::handle_run_event(app, event);
+        });
 }
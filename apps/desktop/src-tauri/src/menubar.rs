@@ -0,0 +1,137 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager, PhysicalPosition, Rect, WebviewWindow, Window, WindowEvent, Wry};
+
+use crate::hotkey;
+use crate::launch;
+use crate::summon::{self, SummonState};
+use crate::windows::{self, MAIN_WINDOW_LABEL};
+
+const TRAY_ID: &str = "menubar";
+const MENU_NEW_CHAT_FROM_CLIPBOARD: &str = "new-chat-from-clipboard";
+const MENU_PAUSE_HOTKEYS: &str = "pause-hotkeys";
+
+/// Tracks whether the main window is currently presented as a popover under
+/// the menubar icon, as opposed to a regular floating window.
+#[derive(Default)]
+pub struct MenubarState {
+    popover_open: AtomicBool,
+}
+
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Nosis")
+        .icon_as_template(true)
+        .menu(&build_menu(app)?)
+        .show_menu_on_left_click(false)
+        .on_menu_event(handle_menu_event)
+        .on_tray_icon_event(handle_tray_event);
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Right-click menu. Every action is implemented natively so it works even
+/// before the webview has loaded.
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let new_chat = MenuItem::with_id(
+        app,
+        MENU_NEW_CHAT_FROM_CLIPBOARD,
+        "New Chat with Clipboard",
+        true,
+        None::<&str>,
+    )?;
+    let pause_hotkeys = CheckMenuItem::with_id(
+        app,
+        MENU_PAUSE_HOTKEYS,
+        "Pause Hotkeys",
+        true,
+        hotkey::is_paused(app),
+        None::<&str>,
+    )?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let quit = PredefinedMenuItem::quit(app, None)?;
+    Menu::with_items(app, &[&new_chat, &pause_hotkeys, &separator, &quit])
+}
+
+/// Rebuilds the menu so check states match backend state changed elsewhere.
+pub fn refresh_menu(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        tray.set_menu(Some(build_menu(app)?))?;
+    }
+    Ok(())
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    let result = match event.id().as_ref() {
+        MENU_NEW_CHAT_FROM_CLIPBOARD => launch::new_chat_from_clipboard(app.clone()),
+        MENU_PAUSE_HOTKEYS => hotkey::set_hotkeys_paused(app.clone(), !hotkey::is_paused(app)),
+        _ => Ok(()),
+    };
+    if let Err(e) = result {
+        eprintln!("menubar action failed: {e}");
+    }
+}
+
+fn handle_tray_event(tray: &TrayIcon, event: TrayIconEvent) {
+    if let TrayIconEvent::Click {
+        button: MouseButton::Left,
+        button_state: MouseButtonState::Up,
+        rect,
+        ..
+    } = event
+    {
+        if let Err(e) = toggle_popover(tray.app_handle(), &rect) {
+            eprintln!("failed to toggle menubar popover: {e}");
+        }
+    }
+}
+
+fn toggle_popover(app: &AppHandle, tray_rect: &Rect) -> tauri::Result<()> {
+    let window = windows::main_window(app)?;
+    let state = app.state::<MenubarState>();
+
+    if window.is_visible()? && state.popover_open.load(Ordering::Acquire) {
+        state.popover_open.store(false, Ordering::Release);
+        return window.hide();
+    }
+
+    anchor_under(&window, tray_rect)?;
+    state.popover_open.store(true, Ordering::Release);
+    summon::summon(app)
+}
+
+/// Centers the window horizontally under the menubar icon, directly below it.
+fn anchor_under(window: &WebviewWindow, tray_rect: &Rect) -> tauri::Result<()> {
+    let scale = window.scale_factor()?;
+    let tray_position = tray_rect.position.to_physical::<f64>(scale);
+    let tray_size = tray_rect.size.to_physical::<f64>(scale);
+    let window_size = window.outer_size()?;
+
+    let x = tray_position.x + tray_size.width / 2.0 - f64::from(window_size.width) / 2.0;
+    let y = tray_position.y + tray_size.height;
+    window.set_position(PhysicalPosition::new(x, y))
+}
+
+/// A popover dismisses itself when focus moves elsewhere, like a native
+/// menubar extra.
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    if let WindowEvent::Focused(false) = event {
+        if window.label() != MAIN_WINDOW_LABEL {
+            return;
+        }
+        let Some(state) = window.try_state::<MenubarState>() else {
+            return;
+        };
+        if window.state::<SummonState>().recently_summoned() {
+            return;
+        }
+        if state.popover_open.swap(false, Ordering::AcqRel) {
+            let _ = window.hide();
+        }
+    }
+}
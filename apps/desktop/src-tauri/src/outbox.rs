@@ -0,0 +1,266 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State, Window};
+
+use crate::error::AppError;
+use crate::files::write_atomic;
+use crate::windows::MAIN_WINDOW_LABEL;
+
+const OUTBOX_FILE: &str = "event-outbox.json";
+/// Long enough to cover a reload or a hidden window, short enough that a
+/// stale launch request isn't replayed the next day.
+const EVENT_TTL: Duration = Duration::from_secs(60 * 60);
+const MAX_PENDING_EVENTS: usize = 100;
+
+/// An event sent to a webview window, kept until the frontend acknowledges it.
+/// The live event carries this same envelope so the frontend can ack by `id`.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingEvent {
+    id: u64,
+    /// Label of the window the event is for. Outboxes written before events
+    /// were routed per window only held events for `main`.
+    #[serde(default = "main_window_label")]
+    window: String,
+    event: String,
+    payload: serde_json::Value,
+    created_at: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OutboxData {
+    next_id: u64,
+    events: Vec<PendingEvent>,
+}
+
+impl OutboxData {
+    /// Starts IDs at the current time rather than 0 so they keep increasing
+    /// when the outbox file is lost or corrupt, and a `since` cursor held by
+    /// the webview never hides newer events.
+    fn seeded(now: u64) -> Self {
+        Self {
+            next_id: now,
+            events: Vec::new(),
+        }
+    }
+
+    fn prune(&mut self, now: u64) {
+        let ttl = EVENT_TTL.as_millis() as u64;
+        self.events
+            .retain(|event| now.saturating_sub(event.created_at) < ttl);
+        let overflow = self.events.len().saturating_sub(MAX_PENDING_EVENTS);
+        self.events.drain(..overflow);
+    }
+
+    /// Events for `window` with an ID after `since`, oldest first.
+    fn since(&self, window: &str, since: Option<u64>) -> Vec<PendingEvent> {
+        self.events
+            .iter()
+            .filter(|event| event.window == window)
+            .filter(|event| since.map_or(true, |since| event.id > since))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Persisted outbox for events emitted while the webview is hidden or
+/// reloading, which would otherwise be lost.
+pub struct EventOutbox {
+    path: PathBuf,
+    data: Mutex<OutboxData>,
+}
+
+fn main_window_label() -> String {
+    MAIN_WINDOW_LABEL.to_owned()
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+impl EventOutbox {
+    pub fn load(app: &AppHandle) -> tauri::Result<Self> {
+        let path = app.path().app_config_dir()?.join(OUTBOX_FILE);
+        let now = now_millis();
+        let mut data: OutboxData = fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_else(|| OutboxData::seeded(now));
+        data.prune(now);
+
+        Ok(Self {
+            path,
+            data: Mutex::new(data),
+        })
+    }
+
+    fn persist(&self, data: &OutboxData) -> Result<(), AppError> {
+        write_atomic(&self.path, &serde_json::to_vec(data)?)?;
+        Ok(())
+    }
+
+    /// Records the event in memory and on disk. Failing to persist only costs
+    /// replay after a restart, so it is logged rather than returned.
+    fn push(&self, window: &str, event: &str, payload: serde_json::Value) -> PendingEvent {
+        let mut data = self.data.lock().unwrap_or_else(|e| e.into_inner());
+        let now = now_millis();
+        data.prune(now);
+
+        let pending = PendingEvent {
+            id: data.next_id,
+            window: window.to_owned(),
+            event: event.to_owned(),
+            payload,
+            created_at: now,
+        };
+        data.next_id += 1;
+        data.events.push(pending.clone());
+        if let Err(e) = self.persist(&data) {
+            eprintln!("failed to persist {OUTBOX_FILE}: {e}");
+        }
+        pending
+    }
+}
+
+/// Emits `event` to the main window; see [`emit_to`].
+pub fn emit<S: Serialize>(app: &AppHandle, event: &str, payload: S) -> Result<(), AppError> {
+    emit_to(app, MAIN_WINDOW_LABEL, event, payload)
+}
+
+/// Emits `event` to the window labeled `window` and records it in the outbox
+/// so it can be replayed with `fetch_pending_events` if the webview missed
+/// it. The live event is sent even when the outbox can't be written.
+pub fn emit_to<S: Serialize>(
+    app: &AppHandle,
+    window: &str,
+    event: &str,
+    payload: S,
+) -> Result<(), AppError> {
+    let payload = serde_json::to_value(payload)?;
+    let pending = match app.try_state::<EventOutbox>() {
+        Some(outbox) => outbox.push(window, event, payload),
+        None => PendingEvent {
+            id: 0,
+            window: window.to_owned(),
+            event: event.to_owned(),
+            payload,
+            created_at: now_millis(),
+        },
+    };
+    app.emit_to(window, event, pending)?;
+    Ok(())
+}
+
+/// Returns the calling window's unacknowledged events newer than `since` (an
+/// event ID), oldest first.
+#[tauri::command]
+pub fn fetch_pending_events(
+    window: Window,
+    outbox: State<'_, EventOutbox>,
+    since: Option<u64>,
+) -> Vec<PendingEvent> {
+    let mut data = outbox.data.lock().unwrap_or_else(|e| e.into_inner());
+    data.prune(now_millis());
+    data.since(window.label(), since)
+}
+
+#[tauri::command]
+pub fn ack_events(outbox: State<'_, EventOutbox>, ids: Vec<u64>) -> Result<(), AppError> {
+    let mut data = outbox.data.lock().unwrap_or_else(|e| e.into_inner());
+    let before = data.events.len();
+    data.events.retain(|event| !ids.contains(&event.id));
+    if data.events.len() != before {
+        outbox.persist(&data)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: u64, created_at: u64) -> PendingEvent {
+        PendingEvent {
+            id,
+            window: MAIN_WINDOW_LABEL.to_owned(),
+            event: "test".to_owned(),
+            payload: serde_json::Value::Null,
+            created_at,
+        }
+    }
+
+    fn ids(events: &[PendingEvent]) -> Vec<u64> {
+        events.iter().map(|event| event.id).collect()
+    }
+
+    #[test]
+    fn prune_drops_expired_events() {
+        let ttl = EVENT_TTL.as_millis() as u64;
+        let now = ttl * 2;
+        let mut data = OutboxData::seeded(now);
+        data.events = vec![event(1, now - ttl), event(2, now - ttl + 1), event(3, now)];
+
+        data.prune(now);
+
+        assert_eq!(ids(&data.events), [2, 3]);
+    }
+
+    #[test]
+    fn prune_drops_oldest_events_past_the_cap() {
+        let now = 1_000;
+        let mut data = OutboxData::seeded(now);
+        data.events = (0..MAX_PENDING_EVENTS as u64 + 5)
+            .map(|id| event(id, now))
+            .collect();
+
+        data.prune(now);
+
+        assert_eq!(data.events.len(), MAX_PENDING_EVENTS);
+        assert_eq!(data.events[0].id, 5);
+        assert_eq!(
+            data.events.last().map(|event| event.id),
+            Some(MAX_PENDING_EVENTS as u64 + 4)
+        );
+    }
+
+    #[test]
+    fn since_is_exclusive() {
+        let mut data = OutboxData::seeded(0);
+        data.events = vec![event(1, 0), event(2, 0), event(3, 0)];
+
+        assert_eq!(ids(&data.since(MAIN_WINDOW_LABEL, None)), [1, 2, 3]);
+        assert_eq!(ids(&data.since(MAIN_WINDOW_LABEL, Some(1))), [2, 3]);
+        assert!(data.since(MAIN_WINDOW_LABEL, Some(3)).is_empty());
+    }
+
+    #[test]
+    fn since_only_returns_events_for_that_window() {
+        let mut data = OutboxData::seeded(0);
+        let mut other = event(2, 0);
+        other.window = "conversation-1".to_owned();
+        data.events = vec![event(1, 0), other, event(3, 0)];
+
+        assert_eq!(ids(&data.since(MAIN_WINDOW_LABEL, None)), [1, 3]);
+        assert_eq!(ids(&data.since("conversation-1", None)), [2]);
+    }
+
+    #[test]
+    fn events_without_a_window_belong_to_main() {
+        let event: PendingEvent = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "event": "new-thread",
+            "payload": null,
+            "createdAt": 0,
+        }))
+        .unwrap();
+
+        assert_eq!(event.window, MAIN_WINDOW_LABEL);
+    }
+}
@@ -0,0 +1,453 @@
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, State, WebviewWindow, Window,
+    WindowEvent,
+};
+
+use crate::error::AppError;
+use crate::settings::SettingsState;
+use crate::summon::SummonState;
+use crate::windows::MAIN_WINDOW_LABEL;
+
+const PRESENTATION_CHANGED_EVENT: &str = "presentation-changed";
+/// Thickness of the collapsed peek bar, in logical pixels.
+const PEEK_BAR_THICKNESS: f64 = 6.0;
+/// Size of the mini card and its gap from the screen corner, in logical pixels.
+const MINI_WIDTH: f64 = 360.0;
+const MINI_HEIGHT: f64 = 240.0;
+const MINI_MARGIN: f64 = 16.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PeekEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MiniCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// How the main window is presented. Sent to the web client with every
+/// change so it can render a collapsed bar or the mini card's latest exchange
+/// instead of the full chat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+pub enum Presentation {
+    /// A regular floating window.
+    Window,
+    /// Docked at a screen edge: a slim bar while collapsed, the full window
+    /// while expanded (on summon, or on hover via `set_peek_expanded`).
+    Peek { edge: PeekEdge, expanded: bool },
+    /// A small always-on-top card in a screen corner showing just the latest
+    /// exchange, until toggled back to the previous mode.
+    Mini { corner: MiniCorner },
+}
+
+/// Main window geometry from before it was docked, re-applied when it goes
+/// back to a regular window.
+#[derive(Clone, Copy)]
+struct Geometry {
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+    always_on_top: bool,
+}
+
+struct Current {
+    presentation: Presentation,
+    restore: Option<Geometry>,
+    /// The mode mini was entered from, returned to when it is toggled off.
+    before_mini: Option<Presentation>,
+}
+
+pub struct PresentationState(Mutex<Current>);
+
+impl Default for PresentationState {
+    fn default() -> Self {
+        Self(Mutex::new(Current {
+            presentation: Presentation::Window,
+            restore: None,
+            before_mini: None,
+        }))
+    }
+}
+
+impl PresentationState {
+    fn get(&self) -> Presentation {
+        self.0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .presentation
+    }
+}
+
+fn capture_geometry(window: &WebviewWindow) -> tauri::Result<Geometry> {
+    Ok(Geometry {
+        position: window.outer_position()?,
+        size: window.outer_size()?,
+        always_on_top: window.is_always_on_top()?,
+    })
+}
+
+fn apply_geometry(window: &WebviewWindow, geometry: &Geometry) -> tauri::Result<()> {
+    window.set_size(geometry.size)?;
+    window.set_position(geometry.position)?;
+    window.set_always_on_top(geometry.always_on_top)
+}
+
+/// Where a window of `size` sits when docked against `edge` of a work area,
+/// centered along that edge.
+fn docked_position(
+    area_position: PhysicalPosition<i32>,
+    area_size: PhysicalSize<u32>,
+    edge: PeekEdge,
+    size: PhysicalSize<u32>,
+) -> PhysicalPosition<i32> {
+    let centered_x = area_position.x + (area_size.width.saturating_sub(size.width) / 2) as i32;
+    let centered_y = area_position.y + (area_size.height.saturating_sub(size.height) / 2) as i32;
+    match edge {
+        PeekEdge::Top => PhysicalPosition::new(centered_x, area_position.y),
+        PeekEdge::Bottom => PhysicalPosition::new(
+            centered_x,
+            area_position.y + area_size.height.saturating_sub(size.height) as i32,
+        ),
+        PeekEdge::Left => PhysicalPosition::new(area_position.x, centered_y),
+        PeekEdge::Right => PhysicalPosition::new(
+            area_position.x + area_size.width.saturating_sub(size.width) as i32,
+            centered_y,
+        ),
+    }
+}
+
+/// Where a window of `size` sits in `corner` of a work area, `margin` in from
+/// both edges.
+fn cornered_position(
+    area_position: PhysicalPosition<i32>,
+    area_size: PhysicalSize<u32>,
+    corner: MiniCorner,
+    size: PhysicalSize<u32>,
+    margin: u32,
+) -> PhysicalPosition<i32> {
+    let left = area_position.x + margin as i32;
+    let top = area_position.y + margin as i32;
+    let right = area_position.x + area_size.width.saturating_sub(size.width + margin) as i32;
+    let bottom = area_position.y + area_size.height.saturating_sub(size.height + margin) as i32;
+    match corner {
+        MiniCorner::TopLeft => PhysicalPosition::new(left, top),
+        MiniCorner::TopRight => PhysicalPosition::new(right, top),
+        MiniCorner::BottomLeft => PhysicalPosition::new(left, bottom),
+        MiniCorner::BottomRight => PhysicalPosition::new(right, bottom),
+    }
+}
+
+/// Shrinks the window to the mini card in `corner` of its monitor's work area.
+fn shrink_to_corner(window: &WebviewWindow, corner: MiniCorner) -> tauri::Result<()> {
+    let Some(monitor) = window.current_monitor()? else {
+        return Ok(());
+    };
+    let area = monitor.work_area();
+    let scale = monitor.scale_factor();
+    let size = PhysicalSize::new(
+        ((MINI_WIDTH * scale).round() as u32).min(area.size.width),
+        ((MINI_HEIGHT * scale).round() as u32).min(area.size.height),
+    );
+    let margin = (MINI_MARGIN * scale).round() as u32;
+
+    window.set_size(size)?;
+    window.set_position(cornered_position(
+        area.position,
+        area.size,
+        corner,
+        size,
+        margin,
+    ))
+}
+
+/// Docks the window against `edge` of its monitor's work area, shrunk to the
+/// peek bar unless `expanded`. `full` is the regular window size.
+fn dock(
+    window: &WebviewWindow,
+    edge: PeekEdge,
+    full: PhysicalSize<u32>,
+    expanded: bool,
+) -> tauri::Result<()> {
+    let Some(monitor) = window.current_monitor()? else {
+        return Ok(());
+    };
+    let area = monitor.work_area();
+    let bar = (PEEK_BAR_THICKNESS * monitor.scale_factor()).round() as u32;
+    let width = full.width.min(area.size.width);
+    let height = full.height.min(area.size.height);
+    let size = match (edge, expanded) {
+        (_, true) => PhysicalSize::new(width, height),
+        (PeekEdge::Top | PeekEdge::Bottom, false) => PhysicalSize::new(width, bar),
+        (PeekEdge::Left | PeekEdge::Right, false) => PhysicalSize::new(bar, height),
+    };
+
+    window.set_size(size)?;
+    window.set_position(docked_position(area.position, area.size, edge, size))
+}
+
+fn notify(app: &AppHandle, presentation: Presentation) {
+    if let Err(e) = app.emit_to(MAIN_WINDOW_LABEL, PRESENTATION_CHANGED_EVENT, presentation) {
+        eprintln!("failed to send presentation change: {e}");
+    }
+}
+
+/// Docks the main window at `edge`, or returns it to a regular window when
+/// `edge` is `None`.
+pub fn peek(app: &AppHandle, edge: Option<PeekEdge>) -> Result<Presentation, AppError> {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) else {
+        return Ok(Presentation::Window);
+    };
+    let state = app.state::<PresentationState>();
+    let mut current = state.0.lock().unwrap_or_else(|e| e.into_inner());
+
+    match edge {
+        Some(edge) => {
+            let restore = match current.restore {
+                Some(restore) => restore,
+                None => capture_geometry(&window)?,
+            };
+            window.set_always_on_top(true)?;
+            dock(&window, edge, restore.size, false)?;
+            current.restore = Some(restore);
+            current.before_mini = None;
+            current.presentation = Presentation::Peek {
+                edge,
+                expanded: false,
+            };
+        }
+        None => {
+            if let Some(restore) = current.restore.take() {
+                apply_geometry(&window, &restore)?;
+            }
+            current.presentation = Presentation::Window;
+            current.before_mini = None;
+        }
+    }
+
+    notify(app, current.presentation);
+    Ok(current.presentation)
+}
+
+/// Shrinks the main window to the mini card in the corner set by `miniCorner`,
+/// or expands it back to the mode it was in before (regular or peek).
+pub fn toggle_mini(app: &AppHandle) -> Result<Presentation, AppError> {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) else {
+        return Ok(Presentation::Window);
+    };
+    let state = app.state::<PresentationState>();
+    let mut current = state.0.lock().unwrap_or_else(|e| e.into_inner());
+
+    if let Presentation::Mini { .. } = current.presentation {
+        let previous = current.before_mini.take().unwrap_or(Presentation::Window);
+        match (previous, current.restore) {
+            (Presentation::Peek { edge, .. }, Some(restore)) => {
+                dock(&window, edge, restore.size, false)?;
+                current.presentation = Presentation::Peek {
+                    edge,
+                    expanded: false,
+                };
+            }
+            (_, restore) => {
+                if let Some(restore) = restore {
+                    apply_geometry(&window, &restore)?;
+                }
+                current.restore = None;
+                current.presentation = Presentation::Window;
+            }
+        }
+    } else {
+        let corner = app
+            .try_state::<SettingsState>()
+            .map(|settings| settings.get().mini_corner)
+            .unwrap_or_default();
+        if current.restore.is_none() {
+            current.restore = Some(capture_geometry(&window)?);
+        }
+        window.set_always_on_top(true)?;
+        shrink_to_corner(&window, corner)?;
+        current.before_mini = Some(current.presentation);
+        current.presentation = Presentation::Mini { corner };
+    }
+
+    notify(app, current.presentation);
+    Ok(current.presentation)
+}
+
+/// Expands or collapses a docked peek window. Does nothing in other modes.
+pub fn expand_peek(app: &AppHandle, expanded: bool) -> tauri::Result<()> {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) else {
+        return Ok(());
+    };
+    let state = app.state::<PresentationState>();
+    let mut current = state.0.lock().unwrap_or_else(|e| e.into_inner());
+    let Presentation::Peek {
+        edge,
+        expanded: was_expanded,
+    } = current.presentation
+    else {
+        return Ok(());
+    };
+    let Some(restore) = current.restore else {
+        return Ok(());
+    };
+    if was_expanded == expanded {
+        return Ok(());
+    }
+
+    dock(&window, edge, restore.size, expanded)?;
+    current.presentation = Presentation::Peek { edge, expanded };
+    notify(app, current.presentation);
+    Ok(())
+}
+
+/// Whether the main window is a regular floating window, as opposed to docked
+/// in a presentation whose geometry is transient.
+pub fn is_windowed(app: &AppHandle) -> bool {
+    app.try_state::<PresentationState>()
+        .map_or(true, |state| state.get() == Presentation::Window)
+}
+
+/// Whether a docked peek window is expanded, or `None` when not docked.
+pub fn peek_expanded(app: &AppHandle) -> Option<bool> {
+    match app.try_state::<PresentationState>()?.get() {
+        Presentation::Peek { expanded, .. } => Some(expanded),
+        _ => None,
+    }
+}
+
+#[tauri::command]
+pub fn get_presentation(state: State<'_, PresentationState>) -> Presentation {
+    state.get()
+}
+
+#[tauri::command]
+pub fn toggle_mini_mode(app: AppHandle) -> Result<Presentation, AppError> {
+    toggle_mini(&app)
+}
+
+#[tauri::command]
+pub fn set_peek_mode(app: AppHandle, edge: Option<PeekEdge>) -> Result<Presentation, AppError> {
+    peek(&app, edge)
+}
+
+/// Lets the web client expand the bar on hover and collapse it on leave,
+/// since the shell gets no pointer events for the window.
+#[tauri::command]
+pub fn set_peek_expanded(app: AppHandle, expanded: bool) -> Result<(), AppError> {
+    expand_peek(&app, expanded)?;
+    Ok(())
+}
+
+/// A peek window collapses when focus moves elsewhere, and every mode resets
+/// when the main window is destroyed (it is recreated as a regular window).
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    if window.label() != MAIN_WINDOW_LABEL {
+        return;
+    }
+    let Some(state) = window.try_state::<PresentationState>() else {
+        return;
+    };
+
+    match event {
+        WindowEvent::Focused(false) => {
+            if window.state::<SummonState>().recently_summoned() {
+                return;
+            }
+            if let Err(e) = expand_peek(window.app_handle(), false) {
+                eprintln!("failed to collapse peek window: {e}");
+            }
+        }
+        WindowEvent::Destroyed => {
+            let mut current = state.0.lock().unwrap_or_else(|e| e.into_inner());
+            current.presentation = Presentation::Window;
+            current.restore = None;
+            current.before_mini = None;
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA_POSITION: PhysicalPosition<i32> = PhysicalPosition { x: 100, y: 50 };
+    const AREA_SIZE: PhysicalSize<u32> = PhysicalSize {
+        width: 1000,
+        height: 800,
+    };
+    const BAR: PhysicalSize<u32> = PhysicalSize {
+        width: 400,
+        height: 10,
+    };
+
+    #[test]
+    fn docks_centered_along_horizontal_edges() {
+        assert_eq!(
+            docked_position(AREA_POSITION, AREA_SIZE, PeekEdge::Top, BAR),
+            PhysicalPosition::new(400, 50)
+        );
+        assert_eq!(
+            docked_position(AREA_POSITION, AREA_SIZE, PeekEdge::Bottom, BAR),
+            PhysicalPosition::new(400, 840)
+        );
+    }
+
+    #[test]
+    fn docks_centered_along_vertical_edges() {
+        let bar = PhysicalSize::new(10, 400);
+        assert_eq!(
+            docked_position(AREA_POSITION, AREA_SIZE, PeekEdge::Left, bar),
+            PhysicalPosition::new(100, 250)
+        );
+        assert_eq!(
+            docked_position(AREA_POSITION, AREA_SIZE, PeekEdge::Right, bar),
+            PhysicalPosition::new(1090, 250)
+        );
+    }
+
+    #[test]
+    fn mini_card_sits_inside_the_margin_of_each_corner() {
+        let card = PhysicalSize::new(300, 200);
+        let position = |corner| cornered_position(AREA_POSITION, AREA_SIZE, corner, card, 20);
+
+        assert_eq!(
+            position(MiniCorner::TopLeft),
+            PhysicalPosition::new(120, 70)
+        );
+        assert_eq!(
+            position(MiniCorner::TopRight),
+            PhysicalPosition::new(780, 70)
+        );
+        assert_eq!(
+            position(MiniCorner::BottomLeft),
+            PhysicalPosition::new(120, 630)
+        );
+        assert_eq!(
+            position(MiniCorner::BottomRight),
+            PhysicalPosition::new(780, 630)
+        );
+    }
+
+    #[test]
+    fn oversized_window_docks_at_the_area_origin() {
+        let size = PhysicalSize::new(2000, 10);
+        assert_eq!(
+            docked_position(AREA_POSITION, AREA_SIZE, PeekEdge::Top, size),
+            PhysicalPosition::new(100, 50)
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::error::AppError;
+use crate::files::write_atomic;
+use crate::hotkey::{self, HotkeyBinding};
+use crate::presentation::MiniCorner;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Desktop-only preferences. Account and chat settings live in the worker;
+/// this file only covers behavior the shell implements natively.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ShellSettings {
+    /// Hide the main window when it loses focus, like a launcher.
+    pub dismiss_on_blur: bool,
+    /// Pressing the summon hotkey while the main window is focused hides it.
+    pub summon_hotkey_toggles: bool,
+    /// Global shortcuts and the action each one triggers.
+    pub hotkeys: Vec<HotkeyBinding>,
+    /// Screen corner the mini card docks to (see `presentation::toggle_mini`).
+    pub mini_corner: MiniCorner,
+}
+
+impl Default for ShellSettings {
+    fn default() -> Self {
+        Self {
+            dismiss_on_blur: false,
+            summon_hotkey_toggles: false,
+            hotkeys: hotkey::default_bindings(),
+            mini_corner: MiniCorner::default(),
+        }
+    }
+}
+
+pub struct SettingsState {
+    path: PathBuf,
+    current: Mutex<ShellSettings>,
+}
+
+impl SettingsState {
+    /// Reads `settings.json` from the app config dir. A missing or malformed
+    /// file falls back to defaults rather than blocking startup.
+    pub fn load(app: &AppHandle) -> tauri::Result<Self> {
+        let path = app.path().app_config_dir()?.join(SETTINGS_FILE);
+        let current = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                eprintln!("ignoring malformed {SETTINGS_FILE}: {e}");
+                ShellSettings::default()
+            }),
+            Err(_) => ShellSettings::default(),
+        };
+
+        Ok(Self {
+            path,
+            current: Mutex::new(current),
+        })
+    }
+
+    pub fn get(&self) -> ShellSettings {
+        self.current
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+#[tauri::command]
+pub fn get_shell_settings(state: State<'_, SettingsState>) -> ShellSettings {
+    state.get()
+}
+
+#[tauri::command]
+pub fn update_shell_settings(
+    app: AppHandle,
+    state: State<'_, SettingsState>,
+    settings: ShellSettings,
+) -> Result<ShellSettings, AppError> {
+    hotkey::validate_bindings(&settings.hotkeys)?;
+    let contents = serde_json::to_vec_pretty(&settings)?;
+
+    // Swap the shortcuts before persisting so a failure leaves both the OS
+    // registration and the file on the previous bindings.
+    let previous = state.get().hotkeys;
+    let unavailable = hotkey::reload(&app, &settings.hotkeys)?;
+    if !unavailable.is_empty() {
+        restore_hotkeys(&app, &previous);
+        return Err(AppError::UnavailableShortcut(unavailable.join(", ")));
+    }
+
+    let mut current = state.current.lock().unwrap_or_else(|e| e.into_inner());
+    if let Err(e) = write_atomic(&state.path, &contents) {
+        restore_hotkeys(&app, &previous);
+        return Err(e.into());
+    }
+    *current = settings.clone();
+    Ok(settings)
+}
+
+fn restore_hotkeys(app: &AppHandle, previous: &[HotkeyBinding]) {
+    if let Err(e) = hotkey::reload(app, previous) {
+        eprintln!("failed to restore previous hotkeys: {e}");
+    }
+}
@@ -0,0 +1,23 @@
+use tauri::{AppHandle, RunEvent};
+use tauri_plugin_global_shortcut::GlobalShortcutExt;
+
+use crate::window_state;
+
+/// Runs teardown once when the event loop exits, instead of relying on drop
+/// order during process teardown.
+pub fn handle_run_event(app: &AppHandle, event: RunEvent) {
+    match event {
+        // Windows are still alive here, unlike at `Exit`.
+        RunEvent::ExitRequested { .. } => window_state::save(app),
+        RunEvent::Exit => shutdown(app),
+        _ => {}
+    }
+}
+
+fn shutdown(app: &AppHandle) {
+    // Release OS-level hotkeys explicitly so a quick relaunch can register
+    // them again without racing the old process.
+    if let Err(e) = app.global_shortcut().unregister_all() {
+        eprintln!("failed to unregister global shortcuts: {e}");
+    }
+}
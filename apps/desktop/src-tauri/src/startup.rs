@@ -0,0 +1,64 @@
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::State;
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupPhase {
+    name: &'static str,
+    duration_ms: f64,
+}
+
+/// Per-phase durations of `setup`, plus the time from `run()` to the start of
+/// setup (plugin initialization and window creation).
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupReport {
+    before_setup_ms: f64,
+    setup_ms: f64,
+    phases: Vec<StartupPhase>,
+}
+
+pub struct StartupTimer {
+    launched: Instant,
+    setup_started: Instant,
+    phases: Vec<StartupPhase>,
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+impl StartupTimer {
+    pub fn new(launched: Instant) -> Self {
+        Self {
+            launched,
+            setup_started: Instant::now(),
+            phases: Vec::new(),
+        }
+    }
+
+    pub fn phase<T>(&mut self, name: &'static str, f: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = f();
+        self.phases.push(StartupPhase {
+            name,
+            duration_ms: millis(started.elapsed()),
+        });
+        result
+    }
+
+    pub fn finish(self) -> StartupReport {
+        StartupReport {
+            before_setup_ms: millis(self.setup_started.duration_since(self.launched)),
+            setup_ms: millis(self.setup_started.elapsed()),
+            phases: self.phases,
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_startup_report(report: State<'_, StartupReport>) -> StartupReport {
+    report.inner().clone()
+}
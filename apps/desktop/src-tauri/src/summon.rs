@@ -0,0 +1,123 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Manager, Window, WindowEvent};
+
+use crate::error::AppError;
+use crate::outbox;
+use crate::presentation;
+use crate::settings::SettingsState;
+use crate::windows::{self, MAIN_WINDOW_LABEL};
+
+const NEW_THREAD_EVENT: &str = "new-thread";
+const RESUME_LAST_THREAD_EVENT: &str = "resume-last-thread";
+
+/// Focus bounces briefly while a window is being brought forward (app
+/// activation order on macOS), so blur events this soon after a summon are
+/// ignored.
+const BLUR_SUPPRESSION: Duration = Duration::from_millis(300);
+
+#[derive(Default)]
+pub struct SummonState {
+    last_summoned: Mutex<Option<Instant>>,
+}
+
+impl SummonState {
+    fn mark(&self) {
+        *self.last_summoned.lock().unwrap_or_else(|e| e.into_inner()) = Some(Instant::now());
+    }
+
+    pub fn recently_summoned(&self) -> bool {
+        self.last_summoned
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some_and(|at| at.elapsed() < BLUR_SUPPRESSION)
+    }
+}
+
+/// Brings the main window to the front (recreating it if it was closed) and
+/// starts the blur suppression window.
+pub fn summon(app: &AppHandle) -> tauri::Result<()> {
+    let window = windows::main_window(app)?;
+    app.state::<SummonState>().mark();
+    window.unminimize()?;
+    presentation::expand_peek(app, true)?;
+    window.show()?;
+    window.set_focus()
+}
+
+/// Hides the main window if it is visible and focused, otherwise summons it.
+/// A peek window collapses to its bar instead of hiding.
+pub fn toggle(app: &AppHandle) -> tauri::Result<()> {
+    let window = windows::main_window(app)?;
+    if let Some(expanded) = presentation::peek_expanded(app) {
+        return if expanded {
+            presentation::expand_peek(app, false)
+        } else {
+            summon(app)
+        };
+    }
+    if window.is_visible()? && window.is_focused()? {
+        window.hide()
+    } else {
+        summon(app)
+    }
+}
+
+/// Starts a new thread in the focused conversation window, or summons the
+/// main window and starts it there.
+pub fn new_thread(app: &AppHandle) -> Result<(), AppError> {
+    if let Some(label) = windows::focused_conversation(app) {
+        return outbox::emit_to(app, &label, NEW_THREAD_EVENT, ());
+    }
+    summon(app)?;
+    outbox::emit(app, NEW_THREAD_EVENT, ())
+}
+
+/// Summons the main window on the most recent thread.
+pub fn resume_last(app: &AppHandle) -> Result<(), AppError> {
+    summon(app)?;
+    outbox::emit(app, RESUME_LAST_THREAD_EVENT, ())
+}
+
+/// Clicking the Dock icon brings back a main window that was left hidden
+/// (dismissed on blur, toggled away, or closed as a menubar popover).
+#[cfg(target_os = "macos")]
+pub fn handle_run_event(app: &AppHandle, event: &tauri::RunEvent) {
+    if let tauri::RunEvent::Reopen {
+        has_visible_windows: false,
+        ..
+    } = event
+    {
+        if let Err(e) = summon(app) {
+            eprintln!("failed to summon window on reopen: {e}");
+        }
+    }
+}
+
+/// Same toggle the summon hotkey uses, exposed for Escape-to-hide in the
+/// webview and other entry points.
+#[tauri::command]
+pub fn toggle_window(app: AppHandle) -> Result<(), AppError> {
+    toggle(&app)?;
+    Ok(())
+}
+
+/// Hides the main window on focus loss when `dismissOnBlur` is enabled. This
+/// runs natively because the webview never sees focus moving to another app.
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    if !matches!(event, WindowEvent::Focused(false)) || window.label() != MAIN_WINDOW_LABEL {
+        return;
+    }
+
+    // A docked window collapses on blur instead (see `presentation.rs`).
+    if !presentation::is_windowed(window.app_handle()) {
+        return;
+    }
+    let dismiss_on_blur = window
+        .try_state::<SettingsState>()
+        .is_some_and(|settings| settings.get().dismiss_on_blur);
+    if dismiss_on_blur && !window.state::<SummonState>().recently_summoned() {
+        let _ = window.hide();
+    }
+}
@@ -0,0 +1,136 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, Manager, PhysicalPosition, PhysicalSize, WebviewWindow, Window, WindowEvent,
+};
+
+use crate::error::AppError;
+use crate::files::write_atomic;
+use crate::presentation;
+use crate::windows::MAIN_WINDOW_LABEL;
+
+const WINDOW_STATE_FILE: &str = "window-state.json";
+/// Ignore saved sizes below this so a corrupt file can't shrink the window to
+/// nothing.
+const MIN_DIMENSION: u32 = 200;
+
+/// Main window geometry and presentation, saved at shutdown and re-applied on
+/// launch. Visibility is not part of it: every cold launch comes from the user
+/// opening the app, and starting hidden would look like the launch failed.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WindowState {
+    monitor: Option<String>,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    always_on_top: bool,
+}
+
+fn state_path(app: &AppHandle) -> tauri::Result<PathBuf> {
+    Ok(app.path().app_config_dir()?.join(WINDOW_STATE_FILE))
+}
+
+fn capture(window: &WebviewWindow) -> tauri::Result<WindowState> {
+    let position = window.outer_position()?;
+    let size = window.inner_size()?;
+    Ok(WindowState {
+        monitor: window
+            .current_monitor()?
+            .and_then(|monitor| monitor.name().cloned()),
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        always_on_top: window.is_always_on_top()?,
+    })
+}
+
+fn try_save(app: &AppHandle) -> Result<(), AppError> {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) else {
+        return Ok(());
+    };
+    // Docked geometry is transient; keep the last regular window state.
+    if !presentation::is_windowed(app) {
+        return Ok(());
+    }
+    let state = capture(&window)?;
+    write_atomic(&state_path(app)?, &serde_json::to_vec_pretty(&state)?)?;
+    Ok(())
+}
+
+/// Best effort: a failed save only means the next launch uses defaults.
+pub fn save(app: &AppHandle) {
+    if let Err(e) = try_save(app) {
+        eprintln!("failed to save window state: {e}");
+    }
+}
+
+/// Only restores the position when the saved monitor is still connected and
+/// the window's top-left corner still lands on it; otherwise the configured
+/// centered position is kept.
+fn position_on_screen(window: &WebviewWindow, state: &WindowState) -> tauri::Result<bool> {
+    Ok(window.available_monitors()?.iter().any(|monitor| {
+        let same_monitor = state.monitor.is_none() || monitor.name() == state.monitor.as_ref();
+        let origin = monitor.position();
+        let size = monitor.size();
+        same_monitor
+            && state.x >= origin.x
+            && state.y >= origin.y
+            && i64::from(state.x) < i64::from(origin.x) + i64::from(size.width)
+            && i64::from(state.y) < i64::from(origin.y) + i64::from(size.height)
+    }))
+}
+
+fn try_restore(app: &AppHandle, window: &WebviewWindow) -> Result<(), AppError> {
+    let bytes = match fs::read(state_path(app)?) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    let state: WindowState = match serde_json::from_slice(&bytes) {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("ignoring malformed {WINDOW_STATE_FILE}: {e}");
+            return Ok(());
+        }
+    };
+
+    if state.width >= MIN_DIMENSION && state.height >= MIN_DIMENSION {
+        window.set_size(PhysicalSize::new(state.width, state.height))?;
+    }
+    if position_on_screen(window, &state)? {
+        window.set_position(PhysicalPosition::new(state.x, state.y))?;
+    }
+    window.set_always_on_top(state.always_on_top)?;
+    Ok(())
+}
+
+/// The main window is created hidden (see `tauri.conf.json`) so it doesn't
+/// flash at the default centered position before moving; it is shown once the
+/// saved geometry is applied, or as configured if that fails.
+pub fn restore(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) else {
+        return;
+    };
+    if let Err(e) = try_restore(app, &window) {
+        eprintln!("failed to restore window state: {e}");
+    }
+    if let Err(e) = window.show() {
+        eprintln!("failed to show main window: {e}");
+    }
+}
+
+/// Closing the main window can end the process before `RunEvent::Exit`, so
+/// its state is saved here as well as in the shutdown hook.
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    if let WindowEvent::CloseRequested { .. } = event {
+        if window.label() == MAIN_WINDOW_LABEL {
+            save(window.app_handle());
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{
+    AppHandle, Manager, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder, Window, WindowEvent,
+};
+
+use crate::error::AppError;
+use crate::window_state;
+
+pub const MAIN_WINDOW_LABEL: &str = "main";
+const CONVERSATION_LABEL_PREFIX: &str = "conversation-";
+const CONVERSATION_WINDOW_WIDTH: f64 = 720.0;
+const CONVERSATION_WINDOW_HEIGHT: f64 = 560.0;
+
+/// Additional conversation windows, keyed by window label.
+#[derive(Default)]
+pub struct ConversationWindows(Mutex<HashMap<String, String>>);
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationWindow {
+    label: String,
+    conversation_id: String,
+}
+
+/// Mirrors the worker's UUID validation so a bad ID fails here instead of
+/// opening a window onto a 404 route.
+pub fn is_valid_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.chars().enumerate().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+fn conversation_label(conversation_id: &str) -> String {
+    format!("{CONVERSATION_LABEL_PREFIX}{conversation_id}")
+}
+
+/// Returns the main window, recreating it from `tauri.conf.json` if it was
+/// closed while conversation windows kept the app running. Without this,
+/// summoning would silently do nothing until a restart.
+pub fn main_window(app: &AppHandle) -> tauri::Result<WebviewWindow> {
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW_LABEL) {
+        return Ok(window);
+    }
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|config| config.label == MAIN_WINDOW_LABEL)
+        .ok_or(tauri::Error::WindowNotFound)?;
+    let window = WebviewWindowBuilder::from_config(app, config)?.build()?;
+    window_state::restore(app);
+    Ok(window)
+}
+
+/// The conversation window the user is currently in, if any, so shell events
+/// like a new thread go to it instead of the main window.
+pub fn focused_conversation(app: &AppHandle) -> Option<String> {
+    app.webview_windows()
+        .into_iter()
+        .find(|(label, window)| {
+            label.starts_with(CONVERSATION_LABEL_PREFIX) && window.is_focused().unwrap_or(false)
+        })
+        .map(|(label, _)| label)
+}
+
+/// Opens a conversation in its own window, or focuses the window already
+/// showing it. Each window loads the web client's conversation route and
+/// receives its own shell events (see `outbox::emit_to`).
+pub fn open_conversation(app: &AppHandle, conversation_id: &str) -> Result<String, AppError> {
+    if !is_valid_uuid(conversation_id) {
+        return Err(AppError::InvalidConversationId);
+    }
+    let conversation_id = conversation_id.to_ascii_lowercase();
+
+    let label = conversation_label(&conversation_id);
+    if let Some(existing) = app.get_webview_window(&label) {
+        existing.unminimize()?;
+        existing.show()?;
+        existing.set_focus()?;
+        return Ok(label);
+    }
+
+    let url = WebviewUrl::App(format!("chat/{conversation_id}").into());
+    WebviewWindowBuilder::new(app, &label, url)
+        .title("Nosis")
+        .inner_size(CONVERSATION_WINDOW_WIDTH, CONVERSATION_WINDOW_HEIGHT)
+        .resizable(true)
+        .build()?;
+
+    app.state::<ConversationWindows>()
+        .0
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(label.clone(), conversation_id);
+
+    Ok(label)
+}
+
+#[tauri::command]
+pub async fn open_conversation_window(
+    app: AppHandle,
+    conversation_id: String,
+) -> Result<String, AppError> {
+    open_conversation(&app, &conversation_id)
+}
+
+#[tauri::command]
+pub fn list_conversation_windows(
+    windows: State<'_, ConversationWindows>,
+) -> Vec<ConversationWindow> {
+    windows
+        .0
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|(label, conversation_id)| ConversationWindow {
+            label: label.clone(),
+            conversation_id: conversation_id.clone(),
+        })
+        .collect()
+}
+
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    if let WindowEvent::Destroyed = event {
+        if window.label().starts_with(CONVERSATION_LABEL_PREFIX) {
+            window
+                .state::<ConversationWindows>()
+                .0
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(window.label());
+        }
+    }
+}